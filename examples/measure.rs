@@ -8,7 +8,9 @@
 #![no_main]
 #![no_std]
 
-use ist_hyt::Hyt;
+// lpc8xx-hal's I²C peripheral still only implements embedded-hal 0.2's `blocking::i2c` traits, so
+// this example uses the `legacy` driver.
+use ist_hyt::legacy::Hyt;
 use lpc8xx_hal::{delay::Delay, i2c, prelude::*, CorePeripherals, Peripherals};
 use panic_rtt_target as _;
 use rtt_target::{rprintln, rtt_init_print};
@@ -83,15 +85,24 @@ fn main() -> ! {
         #[cfg(feature = "i8f24")]
         let (t_fixed, h_fixed) = (m.temperature_i8f24(), m.humidity_i8f24());
 
+        // Floating-point results require an FPU (or a soft-float implementation) to be
+        // efficient, so they're also behind a feature.
+        #[cfg(feature = "f32")]
+        let (t_f32, h_f32) = (m.temperature_f32(), m.humidity_f32());
+
         rprintln!("      Temperature (rounded): {} °C", t_rounded);
         rprintln!("       Temperature (scaled): {}.{:02} °C", t_int, t_frac);
         #[cfg(feature = "i8f24")]
         rprintln!("  Temperature (fixed-point): {:.2} °C", t_fixed);
+        #[cfg(feature = "f32")]
+        rprintln!("        Temperature (float): {:.2} °C", t_f32);
 
         rprintln!("         Humidity (rounded): {} %RH", h_rounded);
         rprintln!("          Humidity (scaled): {}.{:02} %RH", h_int, h_frac);
         #[cfg(feature = "i8f24")]
         rprintln!("     Humidity (fixed-point): {:.2} %RH", h_fixed);
+        #[cfg(feature = "f32")]
+        rprintln!("           Humidity (float): {:.2} %RH", h_f32);
 
         delayer.delay_ms(DELAY_REPEAT_MS);
     }
@@ -0,0 +1,252 @@
+//! An async counterpart to the blocking [`Hyt`](crate::Hyt) driver, for executors (e.g. Embassy)
+//! that support non-blocking I²C transactions, which may be DMA-backed. This lets callers
+//! `.await` each transaction instead of blocking the core for the 60-100ms measurement window.
+//!
+//! The typestate (`mode::Normal`/`mode::Command`) and the measurement conversion logic in
+//! [`Measurement`] are shared with the blocking driver; only the I²C transport is async.
+//!
+//! _This module requires the "async" feature._
+
+use crate::error::HytError;
+use crate::mode;
+use crate::Measurement;
+use core::marker::PhantomData;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+const COMMAND_MODE_BIT: u8 = 0b1000_0000;
+const REGISTER_ADDRESS_MASK: u8 = 0x1f;
+const REGISTER_I2C_ADDRESS: u8 = 0x1f;
+const EEPROM_WRITE_SETTLE_US: u32 = 10_000;
+
+fn command_mode_status(status: u8) -> Result<(), HytError> {
+    if status & COMMAND_MODE_BIT != 0 {
+        Ok(())
+    } else {
+        Err(HytError::NotInCommandMode)
+    }
+}
+
+/// A general error type for the async driver, analogous to [`crate::Error`].
+///
+/// embedded-hal-async's `I2c` trait has a single associated error type, so (as in
+/// [`crate::Error`]) there is only one I²C variant here.
+pub enum Error<I2C>
+where
+    I2C: I2c,
+{
+    I2c(I2C::Error),
+    Hyt(HytError),
+}
+
+impl<I2C> From<HytError> for Error<I2C>
+where
+    I2C: I2c,
+{
+    fn from(other: HytError) -> Self {
+        Self::Hyt(other)
+    }
+}
+
+impl<I2C, I2CError> core::fmt::Debug for Error<I2C>
+where
+    I2C: I2c<Error = I2CError>,
+    I2CError: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::I2c(e) => write!(f, "I2c({:?})", e),
+            Self::Hyt(e) => write!(f, "Hyt({:?})", e),
+        }
+    }
+}
+
+/// The async counterpart to [`crate::Hyt`].
+pub struct Hyt<I2C, Mode>
+where
+    I2C: I2c,
+{
+    _mode: PhantomData<Mode>,
+    i2c: I2C,
+    address: u8,
+    /// Whether a measurement has been requested (via `start_measurement()`) that has not yet
+    /// been retrieved (via `read()`). Only meaningful in [`mode::Normal`].
+    measurement_requested: bool,
+}
+
+impl<I2C> Hyt<I2C, mode::Normal>
+where
+    I2C: I2c,
+{
+    /// Construct a new `Hyt` interface with the factory default I²C address (0x28).
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            _mode: PhantomData,
+            i2c,
+            address: 0x28,
+            measurement_requested: false,
+        }
+    }
+
+    /// Construct a new `Hyt` interface with the specified I²C address.
+    pub fn with_address(self, address: u8) -> Self {
+        Self { address, ..self }
+    }
+
+    /// Attempt to enter command mode. See [`crate::Hyt::enter_command_mode`] for details.
+    pub async fn enter_command_mode(
+        mut self,
+    ) -> Result<Hyt<I2C, mode::Command>, (Self, Error<I2C>)> {
+        // "Start Command Mode"
+        if let Err(e) = self.i2c.write(self.address, &[0xa0, 0x00, 0x00]).await {
+            return Err((self, Error::I2c(e)));
+        }
+
+        // Success is indicated by the command-mode status bit in the next response, so read back
+        // the address register (any register would do) to check it.
+        let mut raw = [0u8; 3];
+        if let Err(e) = self
+            .i2c
+            .write_read(self.address, &[REGISTER_I2C_ADDRESS], &mut raw)
+            .await
+        {
+            return Err((self, Error::I2c(e)));
+        }
+
+        match command_mode_status(raw[0]) {
+            Ok(()) => Ok(Hyt {
+                _mode: PhantomData,
+                i2c: self.i2c,
+                address: self.address,
+                measurement_requested: false,
+            }),
+            Err(e) => Err((self, e.into())),
+        }
+    }
+
+    /// Start a measurement. See [`crate::Hyt::start_measurement`] for details.
+    ///
+    /// If the previous measurement requested was never retrieved with [`read()`](#method.read),
+    /// its (now stale) result is drained first, so that the result of this measurement can be
+    /// unambiguously recognised once it arrives.
+    pub async fn start_measurement(&mut self) -> Result<(), Error<I2C>> {
+        if self.measurement_requested {
+            let mut discard = [0u8; 4];
+            self.i2c
+                .read(self.address, &mut discard)
+                .await
+                .map_err(Error::I2c)?;
+        }
+
+        // "MR (Measurement Request)"
+        self.i2c
+            .write(self.address, &[])
+            .await
+            .map_err(Error::I2c)?;
+        self.measurement_requested = true;
+        Ok(())
+    }
+
+    /// Read the most recent measurement from the sensor. See [`crate::Hyt::read`] for details.
+    ///
+    /// Returns [`HytError::NoMeasurementRequested`] if `start_measurement()` has not been called
+    /// since the last non-stale result was retrieved.
+    pub async fn read(&mut self) -> Result<Measurement, Error<I2C>> {
+        if !self.measurement_requested {
+            return Err(HytError::NoMeasurementRequested.into());
+        }
+
+        let m = self.read_raw().await?;
+        if !m.is_stale() {
+            self.measurement_requested = false;
+        }
+        Ok(m)
+    }
+
+    /// Check whether a previously-[requested](#method.start_measurement) measurement is ready,
+    /// without discarding it: a subsequent [`read()`](#method.read) will still return the result.
+    pub async fn has_data_ready(&mut self) -> Result<bool, Error<I2C>> {
+        if !self.measurement_requested {
+            return Err(HytError::NoMeasurementRequested.into());
+        }
+
+        Ok(!self.read_raw().await?.is_stale())
+    }
+
+    // "DF (Data Fetch)"
+    async fn read_raw(&mut self) -> Result<Measurement, Error<I2C>> {
+        let mut raw = [0u8; 4];
+        self.i2c
+            .read(self.address, &mut raw)
+            .await
+            .map_err(Error::I2c)?;
+        Ok(Measurement::from_raw(raw)?)
+    }
+}
+
+impl<I2C> Hyt<I2C, mode::Command>
+where
+    I2C: I2c,
+{
+    /// Attempt to return to normal mode. See [`crate::Hyt::enter_normal_mode`] for details.
+    pub async fn enter_normal_mode(mut self) -> Result<Hyt<I2C, mode::Normal>, (Self, Error<I2C>)> {
+        // "Start Normal Operation Mode"
+        match self.i2c.write(self.address, &[0x80, 0x00, 0x00]).await {
+            Ok(()) => Ok(Hyt {
+                _mode: PhantomData,
+                i2c: self.i2c,
+                address: self.address,
+                measurement_requested: false,
+            }),
+            Err(e) => Err((self, Error::I2c(e))),
+        }
+    }
+
+    /// Read the sensor's current I²C address from its EEPROM.
+    pub async fn read_address(&mut self) -> Result<u8, Error<I2C>> {
+        Ok(self.read_register(REGISTER_I2C_ADDRESS).await? as u8)
+    }
+
+    /// Reconfigure the sensor's I²C address. See [`crate::Hyt::set_address`] for details.
+    pub async fn set_address<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        address: u8,
+    ) -> Result<(), Error<I2C>> {
+        self.write_register(delay, REGISTER_I2C_ADDRESS, u16::from(address))
+            .await
+    }
+
+    /// Read a raw EEPROM configuration register (`0x00..=0x1f`).
+    pub async fn read_register(&mut self, register: u8) -> Result<u16, Error<I2C>> {
+        assert!(register & !REGISTER_ADDRESS_MASK == 0, "register out of range");
+
+        let mut raw = [0u8; 3];
+        self.i2c
+            .write_read(self.address, &[register], &mut raw)
+            .await
+            .map_err(Error::I2c)?;
+        command_mode_status(raw[0])?;
+        Ok(((raw[1] as u16) << 8) | (raw[2] as u16))
+    }
+
+    /// Write a raw EEPROM configuration register (`0x00..=0x1f`).
+    pub async fn write_register<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        register: u8,
+        value: u16,
+    ) -> Result<(), Error<I2C>> {
+        assert!(register & !REGISTER_ADDRESS_MASK == 0, "register out of range");
+
+        self.i2c
+            .write(
+                self.address,
+                &[0x40 | register, (value >> 8) as u8, value as u8],
+            )
+            .await
+            .map_err(Error::I2c)?;
+        delay.delay_us(EEPROM_WRITE_SETTLE_US).await;
+        Ok(())
+    }
+}
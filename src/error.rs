@@ -1,6 +1,5 @@
 use core::fmt::Debug;
-use embedded_hal as hal;
-use hal::blocking::i2c;
+use embedded_hal::i2c::I2c;
 
 /// An error originating from this crate.
 ///
@@ -10,6 +9,18 @@ use hal::blocking::i2c;
 pub enum HytError {
     MeasurementTakenInCommandMode,
     ScaleValueOutOfBounds,
+    /// A command-mode response arrived with the command-mode status bit clear, meaning the
+    /// sensor is not (or is no longer) in command mode. This can happen if command mode was
+    /// never successfully entered, or if more than 10ms elapsed since power-on before it was
+    /// requested.
+    NotInCommandMode,
+    /// [`Hyt::measure()`](crate::Hyt::measure) did not observe a non-stale measurement within the
+    /// requested timeout.
+    Timeout,
+    /// [`Hyt::read()`](crate::Hyt::read) was called without a preceding
+    /// [`start_measurement()`](crate::Hyt::start_measurement), so there is no result (not even a
+    /// stale one) to report.
+    NoMeasurementRequested,
 }
 
 /// A general error type, including errors originating from this crate (as [`HytError`]) and I²C
@@ -18,32 +29,29 @@ pub enum HytError {
 /// [`HytError`]: ./enum.HytError.html
 pub enum Error<I2C>
 where
-    I2C: i2c::Read + i2c::Write,
+    I2C: I2c,
 {
-    I2CRead(<I2C as i2c::Read>::Error),
-    I2CWrite(<I2C as i2c::Write>::Error),
+    I2c(I2C::Error),
     Hyt(HytError),
 }
 
 impl<I2C> From<HytError> for Error<I2C>
 where
-    I2C: i2c::Read + i2c::Write,
+    I2C: I2c,
 {
     fn from(other: HytError) -> Self {
         Self::Hyt(other)
     }
 }
 
-impl<I2C, I2CReadError, I2CWriteError> Debug for Error<I2C>
+impl<I2C, I2CError> Debug for Error<I2C>
 where
-    I2C: i2c::Read<Error = I2CReadError> + i2c::Write<Error = I2CWriteError>,
-    I2CReadError: Debug,
-    I2CWriteError: Debug,
+    I2C: I2c<Error = I2CError>,
+    I2CError: Debug,
 {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
         match self {
-            Self::I2CRead(e) => write!(f, "I2CReadError({:?})", e),
-            Self::I2CWrite(e) => write!(f, "I2CWriteError({:?})", e),
+            Self::I2c(e) => write!(f, "I2c({:?})", e),
             Self::Hyt(e) => write!(f, "Hyt({:?})", e),
         }
     }
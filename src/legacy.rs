@@ -0,0 +1,273 @@
+//! The original driver, built on embedded-hal 0.2's separate `blocking::i2c::Read` and
+//! `blocking::i2c::Write` traits, for targets whose HAL hasn't yet moved to embedded-hal 1.0's
+//! unified `i2c::I2c` trait.
+//!
+//! This mirrors [`crate::Hyt`] feature-for-feature; only the I²C and delay trait bounds (0.2's
+//! `DelayMs` in place of `DelayNs`), and so the shape of [`Error`], differ. Prefer
+//! [`crate::Hyt`] unless your HAL only implements the 0.2 traits.
+//!
+//! _This module requires the "legacy" feature._
+
+use crate::error::HytError;
+use crate::mode;
+use crate::Measurement;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use embedded_hal_02 as hal;
+use hal::blocking::delay::DelayMs;
+use hal::blocking::i2c;
+
+const COMMAND_MODE_BIT: u8 = 0b1000_0000;
+const REGISTER_ADDRESS_MASK: u8 = 0x1f;
+const REGISTER_I2C_ADDRESS: u8 = 0x1f;
+const EEPROM_WRITE_SETTLE_MS: u8 = 10;
+
+fn command_mode_status(status: u8) -> Result<(), HytError> {
+    if status & COMMAND_MODE_BIT != 0 {
+        Ok(())
+    } else {
+        Err(HytError::NotInCommandMode)
+    }
+}
+
+/// A general error type for the legacy driver, analogous to [`crate::Error`].
+///
+/// embedded-hal 0.2's `Read` and `Write` traits have distinct associated error types, so (unlike
+/// [`crate::Error`]) there are two I²C variants here.
+pub enum Error<I2C>
+where
+    I2C: i2c::Read + i2c::Write,
+{
+    I2CRead(<I2C as i2c::Read>::Error),
+    I2CWrite(<I2C as i2c::Write>::Error),
+    Hyt(HytError),
+}
+
+impl<I2C> From<HytError> for Error<I2C>
+where
+    I2C: i2c::Read + i2c::Write,
+{
+    fn from(other: HytError) -> Self {
+        Self::Hyt(other)
+    }
+}
+
+impl<I2C, I2CReadError, I2CWriteError> Debug for Error<I2C>
+where
+    I2C: i2c::Read<Error = I2CReadError> + i2c::Write<Error = I2CWriteError>,
+    I2CReadError: Debug,
+    I2CWriteError: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::I2CRead(e) => write!(f, "I2CReadError({:?})", e),
+            Self::I2CWrite(e) => write!(f, "I2CWriteError({:?})", e),
+            Self::Hyt(e) => write!(f, "Hyt({:?})", e),
+        }
+    }
+}
+
+/// The legacy (embedded-hal 0.2) counterpart to [`crate::Hyt`].
+pub struct Hyt<I2C, Mode>
+where
+    I2C: i2c::Read + i2c::Write,
+{
+    _mode: PhantomData<Mode>,
+    i2c: I2C,
+    address: u8,
+    measurement_requested: bool,
+}
+
+impl<I2C> Hyt<I2C, mode::Normal>
+where
+    I2C: i2c::Read + i2c::Write,
+{
+    /// Construct a new `Hyt` interface with the factory default I²C address (0x28).
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            _mode: PhantomData,
+            i2c,
+            address: 0x28,
+            measurement_requested: false,
+        }
+    }
+
+    /// Construct a new `Hyt` interface with the specified I²C address.
+    pub fn with_address(self, address: u8) -> Self {
+        Self { address, ..self }
+    }
+
+    /// Attempt to enter command mode. See [`crate::Hyt::enter_command_mode`] for details.
+    pub fn enter_command_mode(mut self) -> Result<Hyt<I2C, mode::Command>, (Self, Error<I2C>)> {
+        // "Start Command Mode"
+        if let Err(e) = self.i2c.write(self.address, &[0xa0, 0x00, 0x00]) {
+            return Err((self, Error::I2CWrite(e)));
+        }
+
+        // Success is indicated by the command-mode status bit in the next response, so read back
+        // the address register (any register would do) to check it.
+        let status = match self.i2c.write(self.address, &[REGISTER_I2C_ADDRESS]) {
+            Ok(()) => {
+                let mut raw = [0u8; 3];
+                match self.i2c.read(self.address, &mut raw) {
+                    Ok(()) => raw[0],
+                    Err(e) => return Err((self, Error::I2CRead(e))),
+                }
+            }
+            Err(e) => return Err((self, Error::I2CWrite(e))),
+        };
+
+        match command_mode_status(status) {
+            Ok(()) => Ok(Hyt {
+                _mode: PhantomData,
+                i2c: self.i2c,
+                address: self.address,
+                measurement_requested: false,
+            }),
+            Err(e) => Err((self, e.into())),
+        }
+    }
+
+    /// Start a measurement. See [`crate::Hyt::start_measurement`] for details.
+    pub fn start_measurement(&mut self) -> Result<(), Error<I2C>> {
+        if self.measurement_requested {
+            let mut discard = [0u8; 4];
+            self.i2c
+                .read(self.address, &mut discard)
+                .map_err(Error::<I2C>::I2CRead)?;
+        }
+
+        // "MR (Measurement Request)"
+        self.i2c
+            .write(self.address, &[])
+            .map_err(Error::<I2C>::I2CWrite)?;
+        self.measurement_requested = true;
+        Ok(())
+    }
+
+    /// Start a measurement and poll until a result is ready, or `timeout_ms` has elapsed. See
+    /// [`crate::Hyt::measure`] for details.
+    pub fn measure<D: DelayMs<u16>>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u16,
+    ) -> Result<Measurement, Error<I2C>> {
+        const INITIAL_DELAY_MS: u16 = 30;
+        const POLL_INTERVAL_MS: u16 = 1;
+
+        self.start_measurement()?;
+        delay.delay_ms(INITIAL_DELAY_MS);
+        let mut elapsed_ms = INITIAL_DELAY_MS;
+
+        loop {
+            let m = self.read()?;
+            if !m.is_stale() {
+                return Ok(m);
+            }
+            if elapsed_ms >= timeout_ms {
+                return Err(HytError::Timeout.into());
+            }
+            delay.delay_ms(POLL_INTERVAL_MS);
+            elapsed_ms = elapsed_ms.saturating_add(POLL_INTERVAL_MS);
+        }
+    }
+
+    /// Read the most recent measurement from the sensor. See [`crate::Hyt::read`] for details.
+    pub fn read(&mut self) -> Result<Measurement, Error<I2C>> {
+        if !self.measurement_requested {
+            return Err(HytError::NoMeasurementRequested.into());
+        }
+
+        let m = self.read_raw()?;
+        if !m.is_stale() {
+            self.measurement_requested = false;
+        }
+        Ok(m)
+    }
+
+    /// Check whether a previously-requested measurement is ready, without discarding it. See
+    /// [`crate::Hyt::has_data_ready`] for details.
+    pub fn has_data_ready(&mut self) -> Result<bool, Error<I2C>> {
+        if !self.measurement_requested {
+            return Err(HytError::NoMeasurementRequested.into());
+        }
+
+        Ok(!self.read_raw()?.is_stale())
+    }
+
+    // "DF (Data Fetch)"
+    fn read_raw(&mut self) -> Result<Measurement, Error<I2C>> {
+        let mut raw = [0u8; 4];
+        self.i2c
+            .read(self.address, &mut raw)
+            .map_err(Error::<I2C>::I2CRead)?;
+        Ok(Measurement::from_raw(raw)?)
+    }
+}
+
+impl<I2C> Hyt<I2C, mode::Command>
+where
+    I2C: i2c::Read + i2c::Write,
+{
+    /// Attempt to return to normal mode. See [`crate::Hyt::enter_normal_mode`] for details.
+    pub fn enter_normal_mode(mut self) -> Result<Hyt<I2C, mode::Normal>, (Self, Error<I2C>)> {
+        // "Start Normal Operation Mode"
+        match self.i2c.write(self.address, &[0x80, 0x00, 0x00]) {
+            Ok(()) => Ok(Hyt {
+                _mode: PhantomData,
+                i2c: self.i2c,
+                address: self.address,
+                measurement_requested: false,
+            }),
+            Err(e) => Err((self, Error::I2CWrite(e))),
+        }
+    }
+
+    /// Read the sensor's current I²C address from its EEPROM.
+    pub fn read_address(&mut self) -> Result<u8, Error<I2C>> {
+        Ok(self.read_register(REGISTER_I2C_ADDRESS)? as u8)
+    }
+
+    /// Reconfigure the sensor's I²C address. See [`crate::Hyt::set_address`] for details.
+    pub fn set_address<D: DelayMs<u8>>(
+        &mut self,
+        delay: &mut D,
+        address: u8,
+    ) -> Result<(), Error<I2C>> {
+        self.write_register(delay, REGISTER_I2C_ADDRESS, u16::from(address))
+    }
+
+    /// Read a raw EEPROM configuration register (`0x00..=0x1f`).
+    pub fn read_register(&mut self, register: u8) -> Result<u16, Error<I2C>> {
+        assert!(register & !REGISTER_ADDRESS_MASK == 0, "register out of range");
+
+        self.i2c
+            .write(self.address, &[register])
+            .map_err(Error::I2CWrite)?;
+        let mut raw = [0u8; 3];
+        self.i2c
+            .read(self.address, &mut raw)
+            .map_err(Error::I2CRead)?;
+        command_mode_status(raw[0])?;
+        Ok(((raw[1] as u16) << 8) | (raw[2] as u16))
+    }
+
+    /// Write a raw EEPROM configuration register (`0x00..=0x1f`).
+    pub fn write_register<D: DelayMs<u8>>(
+        &mut self,
+        delay: &mut D,
+        register: u8,
+        value: u16,
+    ) -> Result<(), Error<I2C>> {
+        assert!(register & !REGISTER_ADDRESS_MASK == 0, "register out of range");
+
+        self.i2c
+            .write(
+                self.address,
+                &[0x40 | register, (value >> 8) as u8, value as u8],
+            )
+            .map_err(Error::I2CWrite)?;
+        delay.delay_ms(EEPROM_WRITE_SETTLE_MS);
+        Ok(())
+    }
+}
@@ -3,16 +3,17 @@
 //!
 //! These sensors have an "I²C-compatible" interface supporting bit rates up to 400kHz.
 //!
-//! This driver uses the I²C traits from [`embedded-hal`][hal_i2c], which currently only support
-//! blocking accesses. To minimise blocking, each function in this crate executes at most one
-//! transaction, the longest of which transfer four bytes.
+//! This driver uses the [`embedded_hal::i2c::I2c`] trait, which only supports blocking accesses.
+//! To minimise blocking, no transaction transfers more than four bytes, and most functions in
+//! this crate execute a single transaction. The exceptions are `enter_command_mode()` (two
+//! transactions), `start_measurement()` (two, when an unread result must first be drained) and
+//! [`Hyt::measure()`], which polls until a result is ready.
 //!
 //! Note that I²C devices can [lock up the bus], preventing these blocking I²C functions from
 //! returning. This crate cannot strictly guarantee that its blocking I²C functions will return at
 //! all.
 //!
 //! [lock up the bus]: https://www.i2c-bus.org/i2c-primer/analysing-obscure-problems/blocked-bus/
-//! [hal_i2c]: https://docs.rs/embedded-hal/0.2.4/embedded_hal/blocking/i2c/index.html
 //! [IST]: https://www.ist-ag.com/
 //! [HYT]: https://www.ist-ag.com/sites/default/files/AHHYTM_E.pdf
 //!
@@ -39,6 +40,9 @@
 //! let temperature = measurement.temperature();
 //! ```
 //!
+//! Callers who don't need to interleave other work whilst the sensor is busy can use
+//! [`Hyt::measure()`] instead, which performs the above poll loop (with a timeout) in one call.
+//!
 //! # Status
 //!
 //! This crate is in early development and its API should be considered to be
@@ -48,22 +52,29 @@
 //!
 //! - Whilst the I²C interface is the same for the whole HYT family, this crate is
 //!   only known to have been tested with the HYT221.
-//! - Support for "command mode" is not yet implemented. Command mode is not
-//!   required for normal operation, but allows configuration, for example, of the
-//!   sensor's I²C address.
-//! - There is not yet any support for non-blocking operations. To mitigate
-//!   this, the `start_measurement()` and `read()` functions are separate, so that
-//!   calling code can do other work whilst the sensor is busy. Note that the
-//!   [embedded-hal] crate doesn't currently provide a non-blocking I²C API.
-//! - Floating-point results are not supported at all, even on microcontrollers that
-//!   can handle them.
+//! - The primary API is blocking. To mitigate this, the `start_measurement()` and `read()`
+//!   functions are separate, so that calling code can do other work whilst the sensor is busy. An
+//!   async variant, built on [embedded-hal-async], is available behind the `async` feature; see
+//!   [`asynch`].
+//! - Floating-point results are available behind the `f32` feature, so targets without an FPU
+//!   don't pay for what they don't use.
 //! - `cargo test` doesn't do anything useful at the moment.
 //!
-//! [embedded-hal]: https://docs.rs/embedded-hal/0.2.4/embedded_hal/
+//! [embedded-hal-async]: https://docs.rs/embedded-hal-async/
+//!
+//! # embedded-hal versions
+//!
+//! This crate targets embedded-hal 1.0's unified [`embedded_hal::i2c::I2c`] trait. HALs that only
+//! implement the 0.2 `blocking::i2c::Read`/`blocking::i2c::Write` traits are still supported via
+//! [`legacy::Hyt`], behind the `legacy` feature.
 
 #![no_std]
 
+#[cfg(feature = "async")]
+pub mod asynch;
 mod error;
+#[cfg(feature = "legacy")]
+pub mod legacy;
 mod measurement;
 
 /// Marker types used to represent the state of the sensor's interface.
@@ -80,21 +91,47 @@ pub use error::HytError;
 pub use measurement::Measurement;
 
 use core::marker::PhantomData;
-use embedded_hal as hal;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+/// Bit 7 of an EEPROM register read (or of a normal-mode measurement) is set while the sensor is
+/// in command mode.
+const COMMAND_MODE_BIT: u8 = 0b1000_0000;
+
+/// EEPROM register addresses are 5 bits wide.
+const REGISTER_ADDRESS_MASK: u8 = 0x1f;
+
+/// The EEPROM register holding the customer-configurable I²C address, in its low byte.
+const REGISTER_I2C_ADDRESS: u8 = 0x1f;
+
+/// How long to wait after an EEPROM write before the next command-mode access, per the
+/// datasheet's write cycle time.
+const EEPROM_WRITE_SETTLE_MS: u32 = 10;
+
+fn command_mode_status(status: u8) -> Result<(), HytError> {
+    if status & COMMAND_MODE_BIT != 0 {
+        Ok(())
+    } else {
+        Err(HytError::NotInCommandMode)
+    }
+}
 
 /// The main sensor interface.
 pub struct Hyt<I2C, Mode>
 where
-    I2C: hal::blocking::i2c::Read + hal::blocking::i2c::Write,
+    I2C: I2c,
 {
     _mode: PhantomData<Mode>,
     i2c: I2C,
     address: u8,
+    /// Whether a measurement has been requested (via `start_measurement()`) that has not yet
+    /// been retrieved (via `read()`). Only meaningful in [`mode::Normal`].
+    measurement_requested: bool,
 }
 
 impl<I2C> Hyt<I2C, mode::Normal>
 where
-    I2C: hal::blocking::i2c::Read + hal::blocking::i2c::Write,
+    I2C: I2c,
 {
     /// Construct a new `Hyt` interface with the factory default I²C address (0x28).
     pub fn new(i2c: I2C) -> Self {
@@ -102,6 +139,7 @@ where
             _mode: PhantomData,
             i2c,
             address: 0x28,
+            measurement_requested: false,
         }
     }
 
@@ -121,51 +159,205 @@ where
     /// programmers reset the MCU using a dedicated nRESET pin, without interrupting the power
     /// supply. If you need to reliably enter command mode, some external logic will be required so
     /// that the sensor can be properly power-cycled.
-    ///
-    /// _**TODO**: Currently unimplemented._
-    pub fn enter_command_mode(self) -> Result<Hyt<I2C, mode::Command>, (Self, Error<I2C>)> {
-        todo!()
+    pub fn enter_command_mode(mut self) -> Result<Hyt<I2C, mode::Command>, (Self, Error<I2C>)> {
+        // "Start Command Mode"
+        if let Err(e) = self.i2c.write(self.address, &[0xa0, 0x00, 0x00]) {
+            return Err((self, Error::I2c(e)));
+        }
+
+        // Success is indicated by the command-mode status bit in the next response, so read back
+        // the address register (any register would do) to check it.
+        let mut raw = [0u8; 3];
+        if let Err(e) =
+            self.i2c
+                .write_read(self.address, &[REGISTER_I2C_ADDRESS], &mut raw)
+        {
+            return Err((self, Error::I2c(e)));
+        }
+
+        match command_mode_status(raw[0]) {
+            Ok(()) => Ok(Hyt {
+                _mode: PhantomData,
+                i2c: self.i2c,
+                address: self.address,
+                measurement_requested: false,
+            }),
+            Err(e) => Err((self, e.into())),
+        }
     }
 
     /// Start a measurement.
     ///
     /// According to the datasheet, it takes 60-100ms for the result to be ready, but in practice
     /// it is often ready after about 40ms.
+    ///
+    /// If the previous measurement requested was never retrieved with [`read()`](#method.read),
+    /// its (now stale) result is drained first, so that the result of this measurement can be
+    /// unambiguously recognised once it arrives.
     pub fn start_measurement(&mut self) -> Result<(), Error<I2C>> {
+        if self.measurement_requested {
+            let mut discard = [0u8; 4];
+            self.i2c
+                .read(self.address, &mut discard)
+                .map_err(Error::I2c)?;
+        }
+
         // "MR (Measurement Request)"
         // This is a simple I²C write, but with no data.
-        // TODO: If we haven't already read the last measurement, read it now, otherwise it won't
-        // appear stale and we won't be able to tell when this measurement is done.
-        self.i2c
-            .write(self.address, &[])
-            .map_err(Error::<I2C>::I2CWrite)
+        self.i2c.write(self.address, &[]).map_err(Error::I2c)?;
+        self.measurement_requested = true;
+        Ok(())
+    }
+
+    /// Start a measurement and poll until a result is ready, or `timeout_ms` has elapsed.
+    ///
+    /// This is a convenience wrapper around [`start_measurement()`](#method.start_measurement)
+    /// and [`read()`](#method.read) for callers who don't need to interleave other work whilst
+    /// the sensor is busy. It waits an initial ~30ms (the result is never ready sooner than that),
+    /// then polls at a 1ms interval until a non-stale [`Measurement`] arrives, returning
+    /// [`HytError::Timeout`] once the accumulated wait reaches `timeout_ms`.
+    ///
+    /// `delay` is taken by mutable reference, rather than by value, so that it can be shared with
+    /// the rest of the application.
+    pub fn measure<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u16,
+    ) -> Result<Measurement, Error<I2C>> {
+        const INITIAL_DELAY_MS: u32 = 30;
+        const POLL_INTERVAL_MS: u32 = 1;
+
+        self.start_measurement()?;
+        delay.delay_ms(INITIAL_DELAY_MS);
+        let mut elapsed_ms = INITIAL_DELAY_MS;
+
+        loop {
+            let m = self.read()?;
+            if !m.is_stale() {
+                return Ok(m);
+            }
+            if elapsed_ms >= u32::from(timeout_ms) {
+                return Err(HytError::Timeout.into());
+            }
+            delay.delay_ms(POLL_INTERVAL_MS);
+            elapsed_ms = elapsed_ms.saturating_add(POLL_INTERVAL_MS);
+        }
     }
 
     /// Read the most recent measurement from the sensor.
     ///
     /// If it has already been read (for example because a recently-started measurement has not yet
     /// completed), the result will be [_stale_](./struct.Measurement.html#method.is_stale).
+    ///
+    /// Returns [`HytError::NoMeasurementRequested`] if [`start_measurement()`] has not been
+    /// called since the last non-stale result was retrieved, since there is then nothing
+    /// meaningful to report.
+    ///
+    /// [`start_measurement()`]: #method.start_measurement
     pub fn read(&mut self) -> Result<Measurement, Error<I2C>> {
-        // "DF (Data Fetch)"
-        // We will read four bytes from the sensor.
-        // TODO: Add support for abandoning stale reads after the first byte, or reading just the
-        // humidity result.
+        if !self.measurement_requested {
+            return Err(HytError::NoMeasurementRequested.into());
+        }
+
+        let m = self.read_raw()?;
+        if !m.is_stale() {
+            self.measurement_requested = false;
+        }
+        Ok(m)
+    }
+
+    /// Check whether a previously-[requested](#method.start_measurement) measurement is ready,
+    /// without discarding it: a subsequent [`read()`](#method.read) will still return the result.
+    pub fn has_data_ready(&mut self) -> Result<bool, Error<I2C>> {
+        if !self.measurement_requested {
+            return Err(HytError::NoMeasurementRequested.into());
+        }
+
+        Ok(!self.read_raw()?.is_stale())
+    }
+
+    // "DF (Data Fetch)"
+    // We will read four bytes from the sensor.
+    // TODO: Add support for abandoning stale reads after the first byte, or reading just the
+    // humidity result.
+    fn read_raw(&mut self) -> Result<Measurement, Error<I2C>> {
         let mut raw = [0u8; 4];
-        self.i2c
-            .read(self.address, &mut raw)
-            .map_err(Error::<I2C>::I2CRead)?;
+        self.i2c.read(self.address, &mut raw).map_err(Error::I2c)?;
         Ok(Measurement::from_raw(raw)?)
     }
 }
 
 impl<I2C> Hyt<I2C, mode::Command>
 where
-    I2C: hal::blocking::i2c::Read + hal::blocking::i2c::Write,
+    I2C: I2c,
 {
     /// Attempt to return to normal mode.
+    pub fn enter_normal_mode(mut self) -> Result<Hyt<I2C, mode::Normal>, (Self, Error<I2C>)> {
+        // "Start Normal Operation Mode"
+        match self.i2c.write(self.address, &[0x80, 0x00, 0x00]) {
+            Ok(()) => Ok(Hyt {
+                _mode: PhantomData,
+                i2c: self.i2c,
+                address: self.address,
+                measurement_requested: false,
+            }),
+            Err(e) => Err((self, Error::I2c(e))),
+        }
+    }
+
+    /// Read the sensor's current I²C address from its EEPROM.
+    ///
+    /// This reflects the address the sensor will use after its next power cycle; see
+    /// [`set_address()`](#method.set_address) for details.
+    pub fn read_address(&mut self) -> Result<u8, Error<I2C>> {
+        Ok(self.read_register(REGISTER_I2C_ADDRESS)? as u8)
+    }
+
+    /// Reconfigure the sensor's I²C address, by writing it to the dedicated EEPROM register.
     ///
-    /// _**TODO**: Currently unimplemented._
-    pub fn enter_normal_mode(self) -> Result<Hyt<I2C, mode::Normal>, (Self, Error<I2C>)> {
-        todo!()
+    /// As with all EEPROM writes, this does not take effect until the sensor is next power
+    /// cycled; this instance (and any others constructed with the old address) will continue to
+    /// use the old address until then. `delay` is used to wait out the EEPROM write cycle time
+    /// before the next command-mode access.
+    pub fn set_address<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        address: u8,
+    ) -> Result<(), Error<I2C>> {
+        self.write_register(delay, REGISTER_I2C_ADDRESS, u16::from(address))
+    }
+
+    /// Read a raw EEPROM configuration register (`0x00..=0x1f`).
+    pub fn read_register(&mut self, register: u8) -> Result<u16, Error<I2C>> {
+        assert!(register & !REGISTER_ADDRESS_MASK == 0, "register out of range");
+
+        let mut raw = [0u8; 3];
+        self.i2c
+            .write_read(self.address, &[register], &mut raw)
+            .map_err(Error::I2c)?;
+        command_mode_status(raw[0])?;
+        Ok(((raw[1] as u16) << 8) | (raw[2] as u16))
+    }
+
+    /// Write a raw EEPROM configuration register (`0x00..=0x1f`).
+    ///
+    /// The write only takes effect after the sensor is next power cycled. `delay` is used to wait
+    /// out the EEPROM write cycle time before the next command-mode access.
+    pub fn write_register<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        register: u8,
+        value: u16,
+    ) -> Result<(), Error<I2C>> {
+        assert!(register & !REGISTER_ADDRESS_MASK == 0, "register out of range");
+
+        self.i2c
+            .write(
+                self.address,
+                &[0x40 | register, (value >> 8) as u8, value as u8],
+            )
+            .map_err(Error::I2c)?;
+        delay.delay_ms(EEPROM_WRITE_SETTLE_MS);
+        Ok(())
     }
 }
@@ -75,6 +75,34 @@ impl Measurement {
         I8F24::from_bits(self.temperature_scaled(1 << 24).unwrap())
     }
 
+    /// Calculate the relative humidity, in %RH, returning the result as a floating-point value.
+    ///
+    /// This is strictly more convenient than [`humidity_scaled()`] for application code that
+    /// already uses floats, at the cost of pulling in floating-point support on targets that
+    /// don't otherwise need it.
+    ///
+    /// _This requires the "f32" feature._
+    ///
+    /// [`humidity_scaled()`]: #method.humidity_scaled
+    #[cfg(feature = "f32")]
+    pub fn humidity_f32(&self) -> f32 {
+        self.humidity_raw() as f32 / RAW_VALUE_MAX as f32 * 100.0
+    }
+
+    /// Calculate the temperature, in °C, returning the result as a floating-point value.
+    ///
+    /// This is strictly more convenient than [`temperature_scaled()`] for application code that
+    /// already uses floats, at the cost of pulling in floating-point support on targets that
+    /// don't otherwise need it.
+    ///
+    /// _This requires the "f32" feature._
+    ///
+    /// [`temperature_scaled()`]: #method.temperature_scaled
+    #[cfg(feature = "f32")]
+    pub fn temperature_f32(&self) -> f32 {
+        self.temperature_raw() as f32 / RAW_VALUE_MAX as f32 * 165.0 - 40.0
+    }
+
     /// Calculate the relative humidity, in %RH, returning the result as a scaled integer.
     ///
     /// This is less convenient than [`humidity_i8f24()`], but passing a `scale` like `10` or `100`